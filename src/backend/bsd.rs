@@ -0,0 +1,59 @@
+//! macOS/BSD backend: enumerates mounts via `getmntinfo(3)`.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::{FsType, MountOptions, MountPoint, Propagation, ReadWrite};
+
+pub(crate) fn read_mounts() -> io::Result<Vec<MountPoint>> {
+    let mut mounts_ptr: *mut libc::statfs = std::ptr::null_mut();
+    let count = unsafe { libc::getmntinfo(&mut mounts_ptr, libc::MNT_WAIT) };
+    // getmntinfo(3) returns the number of mounts, or 0 on error; it never returns a
+    // negative count, so checking `count == 0` is the only way to observe the error
+    // case (this can't distinguish a genuinely mount-less system, same as callers of
+    // getmntinfo(3) generally accept).
+    if count == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let mounts = unsafe { std::slice::from_raw_parts(mounts_ptr, count as usize) };
+    Ok(mounts.iter().map(mount_point_from_statfs).collect())
+}
+
+fn mount_point_from_statfs(stat: &libc::statfs) -> MountPoint {
+    let flags = stat.f_flags as i32;
+    MountPoint {
+        id: None,
+        parent_id: None,
+        major_minor: None,
+        root: None,
+        path: PathBuf::from(cstr_field(&stat.f_mntonname)),
+        what: cstr_field(&stat.f_mntfromname),
+        fstype: FsType::from_str(&cstr_field(&stat.f_fstypename)).unwrap(),
+        options: MountOptions {
+            read_write: if flags & libc::MNT_RDONLY != 0 { ReadWrite::ReadOnly } else { ReadWrite::ReadWrite },
+            nosuid: flags & libc::MNT_NOSUID != 0,
+            nodev: flags & libc::MNT_NODEV != 0,
+            noexec: flags & libc::MNT_NOEXEC != 0,
+            noatime: flags & libc::MNT_NOATIME != 0,
+            sync: flags & libc::MNT_SYNCHRONOUS != 0,
+            // `nodiratime`, `relatime`, `dirsync` and `remount` have no equivalent `MNT_*`
+            // flag in `statfs.f_flags` on macOS/BSD; they are Linux-only mount concepts.
+            nodiratime: false,
+            relatime: false,
+            dirsync: false,
+            remount: false,
+            key_values: BTreeMap::new(),
+            others: Vec::new(),
+        },
+        optional_fields: Vec::new(),
+        propagation: Propagation::Private,
+    }
+}
+
+/// Reads a NUL-terminated, fixed-size C string field out of a `statfs` struct.
+fn cstr_field(bytes: &[libc::c_char]) -> String {
+    let bytes: Vec<u8> = bytes.iter().take_while(|&&b| b != 0).map(|&b| b as u8).collect();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
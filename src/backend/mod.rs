@@ -0,0 +1,13 @@
+//! OS-specific mount enumeration backends for platforms other than Linux, normalized into
+//! the common `MountPoint`/`FsType` types. `id`, `parent_id` and `root` are always `None`
+//! on these backends, since none of them expose a mount id/parent hierarchy like Linux does.
+
+#[cfg(any(target_os = "macos", target_os = "freebsd", target_os = "dragonfly", target_os = "netbsd", target_os = "openbsd"))]
+mod bsd;
+#[cfg(any(target_os = "macos", target_os = "freebsd", target_os = "dragonfly", target_os = "netbsd", target_os = "openbsd"))]
+pub(crate) use bsd::read_mounts;
+
+#[cfg(target_os = "windows")]
+mod windows;
+#[cfg(target_os = "windows")]
+pub(crate) use windows::read_mounts;
@@ -0,0 +1,80 @@
+//! Windows backend: enumerates mounted volumes via the Win32 volume APIs.
+
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::io;
+use std::os::windows::ffi::OsStringExt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use windows_sys::Win32::Storage::FileSystem::{GetLogicalDriveStringsW, GetVolumeInformationW};
+use windows_sys::Win32::System::SystemServices::FILE_READ_ONLY_VOLUME;
+
+use crate::{FsType, MountOptions, MountPoint, Propagation, ReadWrite};
+
+pub(crate) fn read_mounts() -> io::Result<Vec<MountPoint>> {
+    let mut buffer = [0u16; 1024];
+    let len = unsafe { GetLogicalDriveStringsW(buffer.len() as u32, buffer.as_mut_ptr()) };
+    if len == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let mut mounts = Vec::new();
+    for drive in buffer[..len as usize].split(|&c| c == 0).filter(|s| !s.is_empty()) {
+        let drive_root: Vec<u16> = drive.iter().copied().chain(std::iter::once(0)).collect();
+        let mut fs_name = [0u16; 64];
+        let mut volume_name = [0u16; 256];
+        let mut fs_flags: u32 = 0;
+        let ok = unsafe {
+            GetVolumeInformationW(
+                drive_root.as_ptr(),
+                volume_name.as_mut_ptr(),
+                volume_name.len() as u32,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                &mut fs_flags,
+                fs_name.as_mut_ptr(),
+                fs_name.len() as u32,
+            )
+        };
+        if ok == 0 {
+            // Not every drive letter has a volume mounted, e.g. an empty CD-ROM drive.
+            continue;
+        }
+        let read_write = if fs_flags & FILE_READ_ONLY_VOLUME != 0 {
+            ReadWrite::ReadOnly
+        } else {
+            ReadWrite::ReadWrite
+        };
+        mounts.push(MountPoint {
+            id: None,
+            parent_id: None,
+            major_minor: None,
+            root: None,
+            path: PathBuf::from(utf16_to_string(drive)),
+            what: utf16_to_string(&volume_name),
+            fstype: FsType::from_str(&utf16_to_string(&fs_name)).unwrap(),
+            options: MountOptions {
+                read_write,
+                nosuid: false,
+                nodev: false,
+                noexec: false,
+                noatime: false,
+                nodiratime: false,
+                relatime: false,
+                sync: false,
+                dirsync: false,
+                remount: false,
+                key_values: BTreeMap::new(),
+                others: Vec::new(),
+            },
+            optional_fields: Vec::new(),
+            propagation: Propagation::Private,
+        });
+    }
+    Ok(mounts)
+}
+
+fn utf16_to_string(buf: &[u16]) -> String {
+    let end = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+    OsString::from_wide(&buf[..end]).to_string_lossy().into_owned()
+}
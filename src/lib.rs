@@ -26,7 +26,12 @@ use std::path::{Path, PathBuf};
 use std::io::{self, BufRead};
 use std::fs::File;
 use std::fmt;
-use regex::Regex;
+use std::collections::{BTreeMap, HashSet};
+
+mod mount;
+pub use mount::UmountFlags;
+
+mod backend;
 
 /// Some common filesystems types
 /// The String representation must be the same when creating using `from_str`
@@ -104,6 +109,8 @@ pub struct MountPoint {
     pub id: Option<u32>,
     /// The id of the parent mount.
     pub parent_id: Option<u32>,
+    /// The major:minor device number of the underlying device, as shown in `st_dev`.
+    pub major_minor: Option<(u32, u32)>,
     /// The path to the directory that acts as the root for this mount point.
     pub root: Option<PathBuf>,
     // Filesystem-specific information
@@ -114,31 +121,131 @@ pub struct MountPoint {
     pub fstype: FsType,
     /// Some additional mount options
     pub options: MountOptions,
+    /// The optional per-mount tag fields (e.g. `shared:2`, `master:3`, `unbindable`)
+    /// that appear between the mount options and the `-` separator. Always empty
+    /// when read from the `/etc/mtab` fallback, which does not carry this information.
+    pub optional_fields: Vec<String>,
+    /// The mount propagation state, derived from `optional_fields`.
+    pub propagation: Propagation,
+}
+
+/// The mount propagation state of a `MountPoint`, as described in
+/// https://www.kernel.org/doc/Documentation/filesystems/sharedsubtree.txt
+#[derive(Debug, PartialEq)]
+pub enum Propagation {
+    /// Mount and umount events do not propagate in or out of this mount.
+    Private,
+    /// Mount and umount events propagate to and from the other members of peer group `group`.
+    Shared { group: u32 },
+    /// Mount and umount events propagate only from the peer group of the master mount `master`.
+    Slave { master: u32 },
+    /// The mount cannot be bind mounted.
+    Unbindable,
+}
+
+impl Propagation {
+
+    /// Derives the propagation state from the optional tag fields of a mountinfo line.
+    /// Mounts not carrying any recognized tag (e.g. those read from `/etc/mtab`) are `Private`.
+    fn from_optional_fields(fields: &[String]) -> Self {
+        for field in fields {
+            if field == "unbindable" {
+                return Propagation::Unbindable;
+            }
+            if let Some(group) = field.strip_prefix("shared:").and_then(|s| s.parse::<u32>().ok()) {
+                return Propagation::Shared { group };
+            }
+            if let Some(master) = field.strip_prefix("master:").and_then(|s| s.parse::<u32>().ok()) {
+                return Propagation::Slave { master };
+            }
+        }
+        Propagation::Private
+    }
+
 }
 
 impl MountPoint {
     
+    /// The number of space-separated fields that always precede the optional tags:
+    /// <id> <parent_id> <major>:<minor> <root> <mount_point> <mount_options>
+    const FIXED_FIELDS: usize = 6;
+
     /// Creates a new mount point from a line of the `/proc/self/mountinfo` file.
     fn parse_proc_mountinfo_line(line: &String) -> Result<Self, io::Error> {
         // The line format is:
-        // <id> <parent_id> <major>:<minor> <root> <mount_point> <mount_options> <optional tags> "-" <fstype> <mount souce> <super options>
+        // <id> <parent_id> <major>:<minor> <root> <mount_point> <mount_options> <optional tags...> "-" <fstype> <mount source> <super options>
         // Ref: https://www.kernel.org/doc/Documentation/filesystems/proc.txt - /proc/<pid>/mountinfo - Information about mounts
-        let re = Regex::new(r"(\d*)\s(\d*)\s(\d*:\d*)\s([\S]*)\s([\S]*)\s([A-Za-z0-9,]*)\s([A-Za-z0-9:\s]*)\s\- ([\S]*)\s([\S]*)(.*)").unwrap();
-        if !re.is_match(line) {
-            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid format"));
+        // The number of optional tags varies (e.g. "shared:2 master:3"), so instead of a single
+        // monolithic regex we split on whitespace and locate the literal "-" that separates the
+        // per-mount fields from the per-superblock fields.
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let invalid = || io::Error::new(io::ErrorKind::InvalidData, "Invalid format");
+        let separator = fields.iter().position(|&f| f == "-").ok_or_else(invalid)?;
+        if separator < MountPoint::FIXED_FIELDS || fields.len() < separator + 3 {
+            return Err(invalid());
         }
-        let caps = re.captures(line).unwrap();
+
+        let id = fields[0].parse::<u32>().map_err(|_| invalid())?;
+        let parent_id = fields[1].parse::<u32>().map_err(|_| invalid())?;
+        let mut major_minor = fields[2].splitn(2, ':');
+        let major = major_minor.next().and_then(|s| s.parse::<u32>().ok()).ok_or_else(invalid)?;
+        let minor = major_minor.next().and_then(|s| s.parse::<u32>().ok()).ok_or_else(invalid)?;
+
+        let optional_fields: Vec<String> = fields[MountPoint::FIXED_FIELDS..separator].iter().map(|s| s.to_string()).collect();
+        let propagation = Propagation::from_optional_fields(&optional_fields);
+
         Ok(MountPoint {
-            id: Some(caps[1].parse::<u32>().unwrap()),
-            parent_id: Some(caps[2].parse::<u32>().unwrap()),
-            root: Some(PathBuf::from(caps[4].to_string())),
-            path: PathBuf::from(caps[5].to_string()),
-            options: MountOptions::new(&caps[6].to_string()),
-            fstype: FsType::from_str(&caps[8]).unwrap(),
-            what: caps[9].to_string()
+            id: Some(id),
+            parent_id: Some(parent_id),
+            major_minor: Some((major, minor)),
+            root: Some(PathBuf::from(unescape_octal(fields[3]))),
+            path: PathBuf::from(unescape_octal(fields[4])),
+            options: MountOptions::new(fields[5]),
+            optional_fields,
+            propagation,
+            fstype: FsType::from_str(fields[separator + 1]).unwrap(),
+            what: unescape_octal(fields[separator + 2])
         })
     }
 
+    /// Unmounts this mount point directly via `umount2(2)`.
+    pub fn umount(&self, flags: UmountFlags) -> io::Result<()> {
+        mount::umount2(&self.path, flags)
+    }
+
+}
+
+/// The kernel escapes whitespace and backslashes in the `root`, mount point and mount source
+/// fields of `/proc/.../mountinfo` and `/etc/mtab` as octal sequences (e.g. `\040` for a space),
+/// the same way util-linux's mntent handling does. This undoes that escaping, replacing each
+/// `\NNN` octal sequence up to `\377` with its corresponding byte and leaving lone backslashes,
+/// and sequences above `\377` that do not fit in a byte, untouched.
+fn unescape_octal(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut result = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        // Work on bytes only: the three digits after a backslash may straddle a multi-byte
+        // UTF-8 sequence (e.g. `\` followed by a non-ASCII character), so slicing `s` by
+        // these raw offsets would risk panicking on a non-char-boundary index.
+        let is_octal_digit = |b: u8| (b'0'..=b'7').contains(&b);
+        if bytes[i] == b'\\' && i + 3 < bytes.len()
+            && is_octal_digit(bytes[i + 1]) && is_octal_digit(bytes[i + 2]) && is_octal_digit(bytes[i + 3])
+        {
+            // Widen to u32 before combining digits: the first digit alone can be up to 7,
+            // and 7 * 64 already overflows a u8, so doing this math in u8 would panic on
+            // debug builds for any `\4xx`-`\7xx` escape.
+            let value = (bytes[i + 1] - b'0') as u32 * 64 + (bytes[i + 2] - b'0') as u32 * 8 + (bytes[i + 3] - b'0') as u32;
+            if let Ok(byte) = u8::try_from(value) {
+                result.push(byte);
+                i += 4;
+                continue;
+            }
+        }
+        result.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&result).into_owned()
 }
 
 #[derive(Debug)]
@@ -153,35 +260,94 @@ pub enum ReadWrite {
 pub struct MountOptions {
     /// If it was mounted as read-only or read-write.
     pub read_write: ReadWrite,
+    /// Do not honor set-user-ID and set-group-ID bits.
+    pub nosuid: bool,
+    /// Disallow access to device special files.
+    pub nodev: bool,
+    /// Disallow execution of binaries on the mounted filesystem.
+    pub noexec: bool,
+    /// Do not update access times on the filesystem.
+    pub noatime: bool,
+    /// Do not update access times on directories on the filesystem.
+    pub nodiratime: bool,
+    /// Update access times relative to modify/change time.
+    pub relatime: bool,
+    /// All I/O to the filesystem is done synchronously.
+    pub sync: bool,
+    /// Writes to directories on the filesystem are made synchronously.
+    pub dirsync: bool,
+    /// The mount was remounted with different options.
+    pub remount: bool,
+    /// Key/value options, e.g. `size=8026512k` or `nr_inodes=1048576` for tmpfs.
+    pub key_values: BTreeMap<String, String>,
     /// Additional options, not currently parsed by this library.
     pub others: Vec<String>
 }
 
 impl MountOptions {
-    
+
     /// Creates a new mount options from a string.
     /// The string must be a comma-separated list of options.
     pub fn new(options: &str) -> Self {
         let mut read_write = ReadWrite::ReadOnly;
+        let mut nosuid = false;
+        let mut nodev = false;
+        let mut noexec = false;
+        let mut noatime = false;
+        let mut nodiratime = false;
+        let mut relatime = false;
+        let mut sync = false;
+        let mut dirsync = false;
+        let mut remount = false;
+        let mut key_values = BTreeMap::new();
         let mut others = Vec::new();
         for option in options.split(',') {
             match option {
                 "ro" => read_write = ReadWrite::ReadOnly,
                 "rw" => read_write = ReadWrite::ReadWrite,
-                &_ => others.push(option.to_owned())
+                "nosuid" => nosuid = true,
+                "nodev" => nodev = true,
+                "noexec" => noexec = true,
+                "noatime" => noatime = true,
+                "nodiratime" => nodiratime = true,
+                "relatime" => relatime = true,
+                "sync" => sync = true,
+                "dirsync" => dirsync = true,
+                "remount" => remount = true,
+                &_ => match option.split_once('=') {
+                    Some((key, value)) => { key_values.insert(key.to_owned(), value.to_owned()); },
+                    None => others.push(option.to_owned())
+                }
             }
         }
         MountOptions {
             read_write,
+            nosuid,
+            nodev,
+            noexec,
+            noatime,
+            nodiratime,
+            relatime,
+            sync,
+            dirsync,
+            remount,
+            key_values,
             others
         }
     }
 
+    /// Returns the value of a key/value mount option (e.g. `size`, `nr_inodes`), if present.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.key_values.get(key).map(|value| value.as_str())
+    }
+
 }
 
 /// A struct containing the mount information.
-/// Note that it will only contain the mount points visible for the calling process.
-/// If the calling process is inside a chroot, not all mount points will be visible. 
+/// On Linux, this will only contain the mount points visible for the calling process;
+/// if the calling process is inside a chroot, not all mount points will be visible.
+/// On other platforms (macOS, the BSDs, Windows) it contains every mount point the OS
+/// reports, since those platforms have no per-process mount namespace to filter by.
 #[derive(Debug)]
 pub struct MountInfo {
     /// The list of mount points visible for the current process.
@@ -190,26 +356,61 @@ pub struct MountInfo {
 
 impl MountInfo {
 
-    /// The most "modern" file with mount information. Introduced in Linux 2.6.26.
-    /// According to the docs, this should be the most reliable (and up-to-date) way to get the mount information.
-    const MOUNT_INFO_FILE: &'static str = "/proc/self/mountinfo";
-
     /// This file should exists even in ancient versions of the Linux kernel.
     /// We use it as a fallback, if for some reason /proc/self/mountinfo is not available.
     /// Believe it or not, there are still devices running ancient versions of the Linux kernel.
+    #[cfg(target_os = "linux")]
     const MTAB_FILE: &'static str = "/etc/mtab";
 
-    /// Creates a new instance of the MountInfo struct.
-    /// It will read the contents of the /proc/self/mountinfo file, if it exists.
-    /// If it does not exist, it will fall-back to read the contents of the /etc/mtab file.
+    /// Creates a new instance of the MountInfo struct, listing the mount points visible to the
+    /// current process. On Linux this reads `/proc/self/mountinfo`, falling back to `/etc/mtab`.
+    /// On macOS and the BSDs it uses `getmntinfo(3)`; on Windows, the volume APIs.
+    #[cfg(target_os = "linux")]
+    pub fn new() -> Result<Self, io::Error> {
+        MountInfo::from_pid_str("self")
+    }
+
+    /// Creates a new instance of the MountInfo struct, listing the mount points visible to the
+    /// current process. On Linux this reads `/proc/self/mountinfo`, falling back to `/etc/mtab`.
+    /// On macOS and the BSDs it uses `getmntinfo(3)`; on Windows, the volume APIs.
+    #[cfg(not(target_os = "linux"))]
     pub fn new() -> Result<Self, io::Error> {
-        if Path::new(MountInfo::MOUNT_INFO_FILE).exists() {
-            let mut mtab = File::open("/proc/self/mountinfo")?;
+        Ok(MountInfo { mounting_points: backend::read_mounts()? })
+    }
+
+    /// Creates a new instance of the MountInfo struct for an arbitrary process.
+    /// It will read the contents of the /proc/<pid>/mountinfo file, if it exists.
+    /// If it does not exist, it returns a `NotFound` error; there is no /etc/mtab fallback here.
+    /// This is useful to inspect the mount namespace of another process, e.g. a container.
+    /// Only available on Linux, since mount namespaces are a Linux-specific concept.
+    #[cfg(target_os = "linux")]
+    pub fn from_pid(pid: u32) -> Result<Self, io::Error> {
+        MountInfo::from_pid_str(&pid.to_string())
+    }
+
+    /// Creates a new instance of the MountInfo struct from anything that implements `io::Read`,
+    /// parsing it as a `/proc/<pid>/mountinfo` file.
+    pub fn from_reader(reader: &mut dyn io::Read) -> Result<Self, io::Error> {
+        Ok(MountInfo {
+            mounting_points: MountInfo::parse_proc_mountinfo(reader)?
+        })
+    }
+
+    /// Builds the path to `/proc/<pid>/mountinfo`, where `pid` is either a process id or
+    /// the literal `self`, and reads it, falling back to `/etc/mtab` if it is not available.
+    /// The `/etc/mtab` fallback only applies to `self`: for a concrete pid it always describes
+    /// the *calling* process's mounts, never the target's, so using it there would silently
+    /// return the wrong namespace instead of the one `from_pid` was asked to inspect.
+    #[cfg(target_os = "linux")]
+    fn from_pid_str(pid: &str) -> Result<Self, io::Error> {
+        let mountinfo_path = format!("/proc/{}/mountinfo", pid);
+        if Path::new(&mountinfo_path).exists() {
+            let mut mtab = File::open(&mountinfo_path)?;
             return Ok(MountInfo {
                 mounting_points: MountInfo::parse_proc_mountinfo(&mut mtab)?
             })
         }
-        else if Path::new(MountInfo::MTAB_FILE).exists() {
+        else if pid == "self" && Path::new(MountInfo::MTAB_FILE).exists() {
             let mut mtab = File::open(MountInfo::MTAB_FILE)?;
             return Ok(MountInfo {
                 mounting_points: MountInfo::parse_mtab(&mut mtab)?
@@ -235,6 +436,73 @@ impl MountInfo {
             .any(|mts| &mts.path == path.as_ref())
     }
 
+    /// Builds a navigable parent/child hierarchy of the mount points, indexed by `id`.
+    /// If the mount points were read from the `/etc/mtab` fallback (where `id` and `parent_id`
+    /// are always `None`), the resulting tree degrades to a flat list: every mount reports no
+    /// parent and no children, and `root()` returns `None`.
+    pub fn tree(&self) -> MountTree<'_> {
+        MountTree::build(&self.mounting_points)
+    }
+
+    /// Unmounts every mount point at or beneath `path`, child-first, using the `id`/`parent_id`
+    /// tree so that nested mounts are always unmounted before the mounts that contain them.
+    /// Unmounting continues even if one mount fails; every failure is returned, keyed by the
+    /// path of the mount that failed.
+    pub fn umount_recursive<P: AsRef<Path>>(&self, path: P, flags: UmountFlags) -> Vec<(PathBuf, io::Error)> {
+        let path = path.as_ref();
+        let targets: Vec<&MountPoint> = self.mounting_points.iter()
+            .filter(|mp| mp.path.starts_with(path))
+            .collect();
+        let mut errors = Vec::new();
+        for mp in self.leaf_first_order(&targets) {
+            if let Err(err) = mp.umount(flags) {
+                errors.push((mp.path.clone(), err));
+            }
+        }
+        errors
+    }
+
+    /// Orders `mounts` leaf-first using the `id`/`parent_id` hierarchy, falling back to
+    /// reverse path-depth order when ids are not available (e.g. the `/etc/mtab` fallback).
+    fn leaf_first_order<'a>(&'a self, mounts: &[&'a MountPoint]) -> Vec<&'a MountPoint> {
+        if mounts.iter().any(|mp| mp.id.is_none()) {
+            let mut ordered: Vec<&MountPoint> = mounts.to_vec();
+            ordered.sort_by_key(|mp| std::cmp::Reverse(mp.path.components().count()));
+            return ordered;
+        }
+        let tree = self.tree();
+        let ids: HashSet<u32> = mounts.iter().filter_map(|mp| mp.id).collect();
+        let mut visited = HashSet::new();
+        let mut ordered = Vec::new();
+        for mp in mounts {
+            MountInfo::visit_leaf_first(&tree, mp.id.unwrap(), &ids, mounts, &mut visited, &mut ordered);
+        }
+        ordered
+    }
+
+    /// Post-order traversal: visits every descendant of `id` within `ids` before `id` itself,
+    /// so that unmounting `ordered` in order always removes children before their parent.
+    fn visit_leaf_first<'a>(
+        tree: &MountTree<'a>,
+        id: u32,
+        ids: &HashSet<u32>,
+        mounts: &[&'a MountPoint],
+        visited: &mut HashSet<u32>,
+        ordered: &mut Vec<&'a MountPoint>,
+    ) {
+        if !visited.insert(id) {
+            return;
+        }
+        for child in tree.children_of(id) {
+            if let Some(child_id) = child.id.filter(|cid| ids.contains(cid)) {
+                MountInfo::visit_leaf_first(tree, child_id, ids, mounts, visited, ordered);
+            }
+        }
+        if let Some(mp) = mounts.iter().find(|mp| mp.id == Some(id)) {
+            ordered.push(mp);
+        }
+    }
+
     fn parse_proc_mountinfo(file: &mut dyn std::io::Read) -> Result<Vec<MountPoint>, std::io::Error> {
         let mut result = Vec::new();
         let reader = io::BufReader::new(file);
@@ -253,13 +521,16 @@ impl MountInfo {
             let parts: Vec<&str> = l.split_whitespace().collect();
             if !parts.is_empty() {
                 results.push(MountPoint {
-                    what: parts[0].to_string(),
-                    path: PathBuf::from(parts[1]),
+                    what: unescape_octal(parts[0]),
+                    path: PathBuf::from(unescape_octal(parts[1])),
                     fstype: FsType::from_str(parts[2]).unwrap(),
                     options: MountOptions::new(parts[3]),
                     id: None,
                     parent_id: None,
+                    major_minor: None,
                     root: None,
+                    optional_fields: Vec::new(),
+                    propagation: Propagation::Private,
                 })
             }
         }
@@ -268,6 +539,59 @@ impl MountInfo {
 
 }
 
+/// A navigable view of the parent/child mount hierarchy, built by indexing
+/// `MountPoint`s by `id` and linking each one to its `parent_id`.
+/// Borrows its mount points from the `MountInfo` it was built from.
+#[derive(Debug)]
+pub struct MountTree<'a> {
+    mounting_points: &'a [MountPoint],
+    children: BTreeMap<u32, Vec<u32>>,
+    root: Option<u32>,
+}
+
+impl<'a> MountTree<'a> {
+
+    fn build(mounting_points: &'a [MountPoint]) -> Self {
+        let ids: HashSet<u32> = mounting_points.iter().filter_map(|mp| mp.id).collect();
+        let mut children: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+        let mut root = None;
+        for mp in mounting_points {
+            if let (Some(id), Some(parent_id)) = (mp.id, mp.parent_id) {
+                if parent_id != id && ids.contains(&parent_id) {
+                    children.entry(parent_id).or_default().push(id);
+                } else if root.is_none() {
+                    root = Some(id);
+                }
+            }
+        }
+        MountTree { mounting_points, children, root }
+    }
+
+    fn by_id(&self, id: u32) -> Option<&'a MountPoint> {
+        self.mounting_points.iter().find(|mp| mp.id == Some(id))
+    }
+
+    /// Returns the mount points that are direct children of the mount with the given id.
+    /// Returns an empty vector if the mount has no children, or if ids are not available.
+    pub fn children_of(&self, id: u32) -> Vec<&'a MountPoint> {
+        self.children.get(&id)
+            .map(|ids| ids.iter().filter_map(|cid| self.by_id(*cid)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the parent mount point of the mount with the given id, if any.
+    pub fn parent_of(&self, id: u32) -> Option<&'a MountPoint> {
+        let parent_id = self.by_id(id)?.parent_id?;
+        self.by_id(parent_id)
+    }
+
+    /// Returns the root mount point of the hierarchy, if ids are available.
+    pub fn root(&self) -> Option<&'a MountPoint> {
+        self.root.and_then(|id| self.by_id(id))
+    }
+
+}
+
 // unit tests
 #[cfg(test)]
 mod test {
@@ -318,6 +642,138 @@ mod test {
         assert_eq!(mtab.is_mounted("/tmp"), true);
     }
 
+    #[test]
+    fn test_from_pid_current_process() {
+        let pid = std::process::id();
+        let mountinfo = MountInfo::from_pid(pid).unwrap();
+        assert!(!mountinfo.mounting_points.is_empty());
+    }
+
+    #[test]
+    fn test_from_reader() {
+        let mut file = FakeFile { s: "36 35 98:0 /mnt1 /mnt2 rw,noatime master:1 - ext3 /dev/root rw,errors=continue".to_owned(), read: false };
+        let mountinfo = MountInfo::from_reader(&mut file).unwrap();
+        assert_eq!(mountinfo.mounting_points.len(), 1);
+        let mp = &mountinfo.mounting_points[0];
+        assert_eq!(mp.path, PathBuf::from("/mnt2"));
+        assert_eq!(mp.major_minor, Some((98, 0)));
+        assert_eq!(mp.optional_fields, vec!["master:1".to_owned()]);
+        assert_eq!(mp.what, "/dev/root".to_owned());
+    }
+
+    #[test]
+    fn test_from_reader_no_optional_fields() {
+        let mut file = FakeFile { s: "36 35 98:0 / / rw,noatime - ext3 /dev/root rw,errors=continue".to_owned(), read: false };
+        let mountinfo = MountInfo::from_reader(&mut file).unwrap();
+        assert_eq!(mountinfo.mounting_points.len(), 1);
+        assert!(mountinfo.mounting_points[0].optional_fields.is_empty());
+        assert_eq!(mountinfo.mounting_points[0].propagation, Propagation::Private);
+    }
+
+    #[test]
+    fn test_propagation_shared() {
+        let mut file = FakeFile { s: "36 35 98:0 / / rw,noatime shared:2 - ext3 /dev/root rw".to_owned(), read: false };
+        let mountinfo = MountInfo::from_reader(&mut file).unwrap();
+        assert_eq!(mountinfo.mounting_points[0].propagation, Propagation::Shared { group: 2 });
+    }
+
+    #[test]
+    fn test_propagation_slave() {
+        let mut file = FakeFile { s: "36 35 98:0 / / rw,noatime master:3 - ext3 /dev/root rw".to_owned(), read: false };
+        let mountinfo = MountInfo::from_reader(&mut file).unwrap();
+        assert_eq!(mountinfo.mounting_points[0].propagation, Propagation::Slave { master: 3 });
+    }
+
+    #[test]
+    fn test_propagation_unbindable() {
+        let mut file = FakeFile { s: "36 35 98:0 / / rw,noatime unbindable - ext3 /dev/root rw".to_owned(), read: false };
+        let mountinfo = MountInfo::from_reader(&mut file).unwrap();
+        assert_eq!(mountinfo.mounting_points[0].propagation, Propagation::Unbindable);
+    }
+
+    #[test]
+    fn test_from_reader_multiple_optional_fields() {
+        let mut file = FakeFile { s: "36 35 98:0 / / rw,noatime shared:2 master:3 - ext3 /dev/root rw".to_owned(), read: false };
+        let mountinfo = MountInfo::from_reader(&mut file).unwrap();
+        assert_eq!(mountinfo.mounting_points[0].optional_fields, vec!["shared:2".to_owned(), "master:3".to_owned()]);
+    }
+
+    #[test]
+    fn test_tree_flat_list_for_mtab() {
+        let mut file = FakeFile { s: "tmpfs /tmp tmpfs rw,seclabel,nosuid,nodev,size=8026512k,nr_inodes=1048576,inode64 0 0".to_owned(), read: false };
+        let mtab = MountInfo { mounting_points: MountInfo::parse_mtab(&mut file).unwrap() };
+        let tree = mtab.tree();
+        assert!(tree.root().is_none());
+        assert!(tree.children_of(0).is_empty());
+    }
+
+    #[test]
+    fn test_tree_root() {
+        let mut file = FakeFile { s: "36 35 98:0 /mnt1 /mnt2 rw,noatime master:1 - ext3 /dev/root rw,errors=continue".to_owned(), read: false };
+        let mountinfo = MountInfo::from_reader(&mut file).unwrap();
+        let tree = mountinfo.tree();
+        let root = tree.root().expect("mountinfo with ids should have a root mount");
+        assert_eq!(root.id, Some(36));
+    }
+
+    #[test]
+    fn test_unescape_octal() {
+        assert_eq!(unescape_octal(r"/mnt/my\040disk"), "/mnt/my disk");
+        assert_eq!(unescape_octal(r"/mnt/tab\011here"), "/mnt/tab\there");
+        assert_eq!(unescape_octal(r"/mnt/newline\012here"), "/mnt/newline\nhere");
+        assert_eq!(unescape_octal(r"/mnt/back\134slash"), "/mnt/back\\slash");
+        assert_eq!(unescape_octal(r"/mnt/plain"), "/mnt/plain");
+    }
+
+    #[test]
+    fn test_unescape_octal_backslash_before_multibyte_utf8() {
+        assert_eq!(unescape_octal("\\éé"), "\\éé");
+    }
+
+    #[test]
+    fn test_unescape_octal_high_digit_does_not_overflow() {
+        assert_eq!(unescape_octal(r"/mnt/my\500disk"), r"/mnt/my\500disk");
+        assert_eq!(unescape_octal(r"/mnt/my\777disk"), r"/mnt/my\777disk");
+        assert_eq!(unescape_octal(r"/mnt/my\400disk"), r"/mnt/my\400disk");
+    }
+
+    #[test]
+    fn test_load_mount_points_with_escaped_space() {
+        let mut file = FakeFile { s: r"tmpfs /mnt/my\040disk tmpfs rw,seclabel 0 0".to_owned(), read: false };
+        let mount_points = MountInfo::parse_mtab(&mut file).unwrap();
+        assert_eq!(mount_points[0].path, PathBuf::from("/mnt/my disk"));
+    }
+
+    fn make_mount_point(id: u32, parent_id: u32, path: &str) -> MountPoint {
+        MountPoint {
+            id: Some(id),
+            parent_id: Some(parent_id),
+            major_minor: Some((0, 0)),
+            root: Some(PathBuf::from("/")),
+            path: PathBuf::from(path),
+            fstype: FsType::Tmpfs,
+            options: MountOptions::new("rw"),
+            optional_fields: Vec::new(),
+            propagation: Propagation::Private,
+            what: "none".to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_umount_recursive_orders_children_before_parent() {
+        let mountinfo = MountInfo {
+            mounting_points: vec![
+                make_mount_point(1, 0, "/"),
+                make_mount_point(2, 1, "/var"),
+                make_mount_point(3, 2, "/var/lib"),
+            ],
+        };
+        let targets: Vec<&MountPoint> = mountinfo.mounting_points.iter().collect();
+        let ordered = mountinfo.leaf_first_order(&targets);
+        let ordered_ids: Vec<u32> = ordered.iter().map(|mp| mp.id.unwrap()).collect();
+        assert_eq!(ordered_ids, vec![3, 2, 1]);
+    }
+
     #[test]
     fn test_mount_options() {
         let options = MountOptions::new("rw,seclabel,nosuid,nodev,size=8026512k,nr_inodes=1048576,inode64");
@@ -327,4 +783,25 @@ mod test {
         assert_eq!(more_options.read_write, ReadWrite::ReadOnly);
         assert_ne!(more_options.others.len(), 0);
     }
+
+    #[test]
+    fn test_mount_options_typed_flags() {
+        let options = MountOptions::new("rw,nosuid,nodev,noexec,relatime");
+        assert!(options.nosuid);
+        assert!(options.nodev);
+        assert!(options.noexec);
+        assert!(options.relatime);
+        assert!(!options.noatime);
+        assert!(!options.sync);
+    }
+
+    #[test]
+    fn test_mount_options_key_values() {
+        let options = MountOptions::new("rw,seclabel,nosuid,nodev,size=8026512k,nr_inodes=1048576,inode64");
+        assert_eq!(options.get("size"), Some("8026512k"));
+        assert_eq!(options.get("nr_inodes"), Some("1048576"));
+        assert_eq!(options.get("missing"), None);
+        assert!(options.others.contains(&"seclabel".to_owned()));
+        assert!(options.others.contains(&"inode64".to_owned()));
+    }
 }
@@ -0,0 +1,41 @@
+//! Wraps the platform unmount syscall used to unmount a single mount point.
+
+use std::io;
+use std::path::Path;
+
+/// Flags controlling how a mount point is unmounted.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct UmountFlags {
+    /// Force unmount even if the mount point is busy (`MNT_FORCE`).
+    pub force: bool,
+    /// Lazy unmount: detach the mount point now, clean it up once it is no longer busy (`MNT_DETACH`).
+    pub detach: bool,
+}
+
+/// Unmounts the filesystem mounted at `path` via `umount2(2)`.
+#[cfg(target_os = "linux")]
+pub(crate) fn umount2<P: AsRef<Path>>(path: P, flags: UmountFlags) -> io::Result<()> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_ref().as_os_str().as_bytes())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "path contains an interior nul byte"))?;
+    let mut raw_flags = 0;
+    if flags.force {
+        raw_flags |= libc::MNT_FORCE;
+    }
+    if flags.detach {
+        raw_flags |= libc::MNT_DETACH;
+    }
+    let ret = unsafe { libc::umount2(c_path.as_ptr(), raw_flags) };
+    if ret != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// `umount2(2)` is Linux-specific; other platforms have no equivalent wired up yet.
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn umount2<P: AsRef<Path>>(_path: P, _flags: UmountFlags) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "umount is only implemented on Linux"))
+}